@@ -0,0 +1,24 @@
+use ethereum_consensus::{
+    deneb::mainnet::BlobsBundle,
+    primitives::{ExecutionAddress, U256},
+    state_transition::Context,
+};
+use mev_rs::{
+    types::{BidRequest, ExecutionPayload},
+    Error,
+};
+
+/// A source of execution payloads the relay can turn into builder bids.
+///
+/// Implementors return the payload together with the blobs bundle committed to (empty before
+/// Deneb), the value delivered to the proposer, and the parent block's gas limit, which the relay
+/// uses to bound the EIP-1559 gas-limit adjustment.
+pub trait Builder {
+    fn get_payload_with_value(
+        &self,
+        bid_request: &BidRequest,
+        fee_recipient: &ExecutionAddress,
+        gas_limit: u64,
+        context: &Context,
+    ) -> Result<(ExecutionPayload, BlobsBundle, U256, u64), Error>;
+}
@@ -6,6 +6,12 @@ mod payload;
 mod service;
 mod utils;
 mod greedy;
+mod builder;
+mod null_builder;
+mod mock_builder;
 
 pub use crate::error::Error;
+pub use builder::Builder;
+pub use mock_builder::MockBuilder;
+pub use null_builder::NullBuilder;
 pub use service::{launch, Config};
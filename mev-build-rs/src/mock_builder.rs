@@ -0,0 +1,107 @@
+use ethereum_consensus::{
+    bellatrix::mainnet as bellatrix,
+    capella::mainnet as capella,
+    deneb::mainnet::BlobsBundle,
+    primitives::{ExecutionAddress, Hash32, U256},
+    state_transition::Context,
+};
+use mev_rs::{
+    types::{BidRequest, ExecutionPayload},
+    Error,
+};
+
+use crate::Builder;
+
+/// A configurable, fault-injecting builder for exercising the relay's validation guards.
+///
+/// A [`MockBuilder`] produces a single, fully-formed payload that tests can mutate through the
+/// `*_mut` accessors to deliberately violate a relay invariant (a gas limit outside the adjustment
+/// window, a mismatched block hash, an omitted proposer payment, ...) and confirm the corresponding
+/// guard rejects the bid.
+#[derive(Clone, Debug)]
+pub struct MockBuilder {
+    payload: ExecutionPayload,
+    blobs_bundle: BlobsBundle,
+    value: U256,
+    parent_gas_limit: u64,
+}
+
+impl MockBuilder {
+    /// A Bellatrix builder whose payload pays `value` to the proposer and sits exactly on the
+    /// parent gas limit, i.e. (for a non-zero `value` and a proposer whose preferred gas limit is
+    /// the parent's) a bid that passes the relay's payload checks until deliberately tampered with.
+    pub fn bellatrix(parent_gas_limit: u64, value: U256) -> Self {
+        let payload = bellatrix::ExecutionPayload {
+            gas_limit: parent_gas_limit,
+            ..Default::default()
+        };
+        Self {
+            payload: ExecutionPayload::Bellatrix(payload),
+            blobs_bundle: BlobsBundle::default(),
+            value,
+            parent_gas_limit,
+        }
+    }
+
+    /// A Capella builder; like [`MockBuilder::bellatrix`] but carrying an (empty) withdrawals list.
+    pub fn capella(parent_gas_limit: u64, value: U256) -> Self {
+        let payload = capella::ExecutionPayload {
+            gas_limit: parent_gas_limit,
+            ..Default::default()
+        };
+        Self {
+            payload: ExecutionPayload::Capella(payload),
+            blobs_bundle: BlobsBundle::default(),
+            value,
+            parent_gas_limit,
+        }
+    }
+
+    pub fn value_mut(&mut self) -> &mut U256 {
+        &mut self.value
+    }
+
+    pub fn gas_limit_mut(&mut self) -> &mut u64 {
+        match &mut self.payload {
+            ExecutionPayload::Bellatrix(payload) => &mut payload.gas_limit,
+            ExecutionPayload::Capella(payload) => &mut payload.gas_limit,
+            ExecutionPayload::Deneb(payload) => &mut payload.gas_limit,
+        }
+    }
+
+    pub fn fee_recipient_mut(&mut self) -> &mut ExecutionAddress {
+        match &mut self.payload {
+            ExecutionPayload::Bellatrix(payload) => &mut payload.fee_recipient,
+            ExecutionPayload::Capella(payload) => &mut payload.fee_recipient,
+            ExecutionPayload::Deneb(payload) => &mut payload.fee_recipient,
+        }
+    }
+
+    pub fn block_hash_mut(&mut self) -> &mut Hash32 {
+        match &mut self.payload {
+            ExecutionPayload::Bellatrix(payload) => &mut payload.block_hash,
+            ExecutionPayload::Capella(payload) => &mut payload.block_hash,
+            ExecutionPayload::Deneb(payload) => &mut payload.block_hash,
+        }
+    }
+
+    pub fn parent_hash_mut(&mut self) -> &mut Hash32 {
+        match &mut self.payload {
+            ExecutionPayload::Bellatrix(payload) => &mut payload.parent_hash,
+            ExecutionPayload::Capella(payload) => &mut payload.parent_hash,
+            ExecutionPayload::Deneb(payload) => &mut payload.parent_hash,
+        }
+    }
+}
+
+impl Builder for MockBuilder {
+    fn get_payload_with_value(
+        &self,
+        _bid_request: &BidRequest,
+        _fee_recipient: &ExecutionAddress,
+        _gas_limit: u64,
+        _context: &Context,
+    ) -> Result<(ExecutionPayload, BlobsBundle, U256, u64), Error> {
+        Ok((self.payload.clone(), self.blobs_bundle.clone(), self.value, self.parent_gas_limit))
+    }
+}
@@ -0,0 +1,33 @@
+use ethereum_consensus::{
+    deneb::mainnet::BlobsBundle,
+    primitives::{ExecutionAddress, U256},
+    state_transition::Context,
+};
+use mev_rs::{
+    types::{BidRequest, ExecutionPayload},
+    Error,
+};
+
+use crate::Builder;
+
+/// A placeholder builder that produces no payloads of its own.
+///
+/// It exists so the relay can be wired end-to-end against a beacon node without
+/// a co-located block builder. It produces no payloads: every call to
+/// [`NullBuilder::get_payload_with_value`] panics.
+#[derive(Clone)]
+pub struct NullBuilder;
+
+impl Builder for NullBuilder {
+    fn get_payload_with_value(
+        &self,
+        _bid_request: &BidRequest,
+        _fee_recipient: &ExecutionAddress,
+        _gas_limit: u64,
+        _context: &Context,
+    ) -> Result<(ExecutionPayload, BlobsBundle, U256, u64), Error> {
+        // The final element is the parent block's gas limit, used by the relay to bound the
+        // EIP-1559 gas-limit adjustment toward the proposer's preference.
+        unimplemented!("the null builder does not produce payloads")
+    }
+}
@@ -4,20 +4,28 @@ use ethereum_consensus::{
     builder::ValidatorRegistration,
     clock::get_current_unix_time_in_secs,
     crypto::SecretKey,
-    primitives::{BlsPublicKey, Hash32, Root, Slot, U256},
+    deneb::mainnet::BlobsBundle,
+    kzg::{verify_blob_kzg_proof, KzgCommitment, KzgProof},
+    primitives::{Blob, BlsPublicKey, Hash32, Root, Slot, U256},
+    ssz::prelude::HashTreeRoot,
     state_transition::Context,
+    Fork,
 };
-use mev_build_rs::NullBuilder;
+use mev_build_rs::{Builder, NullBuilder};
 use mev_rs::{
     signing::sign_builder_message,
     types::{
-        bellatrix, capella, BidRequest, ExecutionPayload, ExecutionPayloadHeader,
+        bellatrix, capella, deneb, BidRequest, ExecutionPayload, ExecutionPayloadHeader,
         SignedBlindedBeaconBlock, SignedBuilderBid, SignedValidatorRegistration,
     },
     BlindedBlockProvider, Error, ValidatorRegistry,
 };
 use parking_lot::Mutex;
-use std::{collections::HashMap, ops::Deref, sync::Arc};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    ops::Deref,
+    sync::Arc,
+};
 
 // `PROPOSAL_TOLERANCE_DELAY` controls how aggresively the relay drops "old" execution payloads
 // once they have been fetched from builders -- currently in response to an incoming request from a
@@ -31,10 +39,9 @@ fn validate_bid_request(
     context: &Context,
     validator_registry: &ValidatorRegistry,
 ) -> Result<(), Error> {
-    // TDOD: take this as input
-    let fork = "Bellatrix";
+    let fork = fork_for_slot(bid_request.slot, context)?;
     // Check if the slot is timely
-    if !is_slot_timely(&bid_request.slot, &fork, &context) {
+    if !is_slot_timely(&bid_request.slot, fork, &context) {
         return Err(Error::InvalidSlot)
     }
 
@@ -51,12 +58,29 @@ fn validate_bid_request(
     Ok(())
 }
 
-fn is_slot_timely(slot: &Slot, fork: &str, context: &Context) -> bool {
-    let current_slot = match fork {
-        "Bellatrix" => 32 + context.bellatrix_fork_epoch * context.slots_per_epoch,
-        "Capella" => 32 + context.capella_fork_epoch * context.slots_per_epoch,
-        _ => unimplemented!(),
+// Map a slot to the fork active at that slot, rejecting pre-Bellatrix slots which the relay
+// cannot serve bids for.
+fn fork_for_slot(slot: Slot, context: &Context) -> Result<Fork, Error> {
+    let epoch = slot / context.slots_per_epoch;
+    if epoch >= context.deneb_fork_epoch {
+        Ok(Fork::Deneb)
+    } else if epoch >= context.capella_fork_epoch {
+        Ok(Fork::Capella)
+    } else if epoch >= context.bellatrix_fork_epoch {
+        Ok(Fork::Bellatrix)
+    } else {
+        Err(Error::UnsupportedFork(slot))
+    }
+}
+
+fn is_slot_timely(slot: &Slot, fork: Fork, context: &Context) -> bool {
+    let fork_epoch = match fork {
+        Fork::Bellatrix => context.bellatrix_fork_epoch,
+        Fork::Capella => context.capella_fork_epoch,
+        Fork::Deneb => context.deneb_fork_epoch,
+        _ => unreachable!("fork_for_slot only yields post-merge forks"),
     };
+    let current_slot = 32 + fork_epoch * context.slots_per_epoch;
     slot + PROPOSAL_TOLERANCE_DELAY >= current_slot
 }
 
@@ -73,24 +97,93 @@ fn is_valid_proposer(public_key: &BlsPublicKey, validator_registry: &ValidatorRe
 
 fn validate_execution_payload(
     execution_payload: &ExecutionPayload,
-    _value: &U256,
+    value: &U256,
     preferences: &ValidatorRegistration,
+    parent_gas_limit: u64,
 ) -> Result<(), Error> {
-    // TODO validations
+    // A bid that does not pay the proposer is never acceptable; reject a builder that omits the
+    // proposer payment outright rather than signing over a worthless bid.
+    if value.is_zero() {
+        return Err(Error::InvalidPayment)
+    }
 
-    // TODO allow for "adjustment cap" per the protocol rules
-    // towards the proposer's preference
-    if execution_payload.gas_limit() != preferences.gas_limit {
+    // The gas limit may only move toward the proposer's preference by a bounded step each block,
+    // per the EIP-1559 gas-limit adjustment rule. Clamp the proposer's `target` into the window
+    // reachable from the parent block and require the payload to match the clamped value.
+    let target = preferences.gas_limit;
+    // The block may move by at most `parent / 1024` gas, exclusive, so the reachable window is
+    // `[parent - parent/1024 + 1, parent + parent/1024 - 1]`. Use saturating arithmetic so a
+    // degenerate parent (`parent < 1024`, i.e. `max_delta == 0`) collapses the window to
+    // `[parent, parent]` rather than underflowing or inverting the bounds.
+    let max_delta = parent_gas_limit / 1024;
+    let span = max_delta.saturating_sub(1);
+    let lower_bound = parent_gas_limit.saturating_sub(span);
+    let upper_bound = parent_gas_limit.saturating_add(span);
+    let expected_gas_limit = target.clamp(lower_bound, upper_bound);
+    if execution_payload.gas_limit() != expected_gas_limit {
         return Err(Error::InvalidGasLimit)
     }
 
-    // verify payload is valid
+    // From Capella onwards the payload carries withdrawals; make sure they are well-formed
+    // before we commit to the header built from this payload.
+    validate_withdrawals(execution_payload)?;
 
-    // verify payload sends `value` to proposer
+    Ok(())
+}
 
+// Sanity-check the payload's withdrawals and return their SSZ root, or `None` for pre-Capella
+// payloads which carry no withdrawals list.
+fn validate_withdrawals(execution_payload: &ExecutionPayload) -> Result<Option<Root>, Error> {
+    let Some(withdrawals) = execution_payload.withdrawals() else { return Ok(None) };
+
+    // Withdrawal indices are assigned monotonically by the consensus layer.
+    let mut last_index: Option<u64> = None;
+    for withdrawal in withdrawals.iter() {
+        if let Some(last) = last_index {
+            if withdrawal.index <= last {
+                return Err(Error::InvalidWithdrawals)
+            }
+        }
+        last_index = Some(withdrawal.index);
+    }
+
+    let mut withdrawals = withdrawals.clone();
+    let withdrawals_root = withdrawals.hash_tree_root().map_err(Error::from)?;
+    Ok(Some(withdrawals_root))
+}
+
+fn validate_blobs_bundle(blobs_bundle: &BlobsBundle, context: &Context) -> Result<(), Error> {
+    let commitments = &blobs_bundle.commitments;
+    let proofs = &blobs_bundle.proofs;
+    let blobs = &blobs_bundle.blobs;
+    if commitments.len() != blobs.len() || proofs.len() != blobs.len() {
+        return Err(Error::InvalidBlobsBundle)
+    }
+    // Pre-Deneb bids carry an empty bundle; avoid loading the KZG trusted setup for them so
+    // serving works even where the `Context` has no setup available.
+    if blobs.is_empty() {
+        return Ok(())
+    }
+    let setup = context.kzg_settings().map_err(Error::from)?;
+    for ((blob, commitment), proof) in blobs.iter().zip(commitments.iter()).zip(proofs.iter()) {
+        verify_blob_kzg_commitment(blob, commitment, proof, setup)?;
+    }
     Ok(())
 }
 
+fn verify_blob_kzg_commitment(
+    blob: &Blob,
+    commitment: &KzgCommitment,
+    proof: &KzgProof,
+    setup: &ethereum_consensus::kzg::KzgSettings,
+) -> Result<(), Error> {
+    if verify_blob_kzg_proof(blob, commitment, proof, setup).map_err(Error::from)? {
+        Ok(())
+    } else {
+        Err(Error::InvalidBlobsBundle)
+    }
+}
+
 fn validate_signed_block(
     signed_block: &mut SignedBlindedBeaconBlock,
     public_key: &BlsPublicKey,
@@ -104,6 +197,16 @@ fn validate_signed_block(
         return Err(Error::UnknownBlock)
     }
 
+    // Ensure the withdrawals in the full payload match the commitment the proposer signed, so a
+    // builder cannot swap withdrawals between the header and the revealed payload.
+    if let Some(local_withdrawals_root) = validate_withdrawals(local_payload)? {
+        let signed_withdrawals_root =
+            signed_block.withdrawals_root().ok_or(Error::InvalidWithdrawals)?;
+        if signed_withdrawals_root != &local_withdrawals_root {
+            return Err(Error::InvalidWithdrawals)
+        }
+    }
+
     // OPTIONAL:
     // -- verify w/ consensus?
     // verify slot is timely
@@ -113,22 +216,27 @@ fn validate_signed_block(
     signed_block.verify_signature(public_key, *genesis_validators_root, context).map_err(From::from)
 }
 
-#[derive(Clone)]
-pub struct Relay(Arc<Inner>);
+pub struct Relay<B = NullBuilder>(Arc<Inner<B>>);
 
-impl Deref for Relay {
-    type Target = Inner;
+impl<B> Clone for Relay<B> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<B> Deref for Relay<B> {
+    type Target = Inner<B>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-pub struct Inner {
+pub struct Inner<B> {
     secret_key: SecretKey,
     public_key: BlsPublicKey,
     genesis_validators_root: Root,
-    builder: NullBuilder,
+    builder: B,
     validator_registry: ValidatorRegistry,
     context: Arc<Context>,
     state: Mutex<State>,
@@ -137,6 +245,46 @@ pub struct Inner {
 #[derive(Debug, Default)]
 struct State {
     execution_payloads: HashMap<BidRequest, ExecutionPayload>,
+    // Blobs bundles are only produced from Deneb onwards; pre-Deneb bids have no entry here.
+    blobs_bundles: HashMap<BidRequest, BlobsBundle>,
+    // Gas limit of each block hash the relay has served, so a child block's gas-limit adjustment
+    // can be bounded against a parent limit the relay itself observed rather than one the
+    // (untrusted) builder self-reports. Entries are pruned alongside the execution payloads.
+    parent_gas_limits: HashMap<Hash32, (Slot, u64)>,
+    proposal_protection: ProposalProtection,
+}
+
+// Anti-equivocation store modeled on validator slashing protection: the relay records the block
+// root it reveals for each `(slot, proposer)` pair and refuses to unblind a *different* block for
+// a pair it has already served. The proposer pubkey is kept in its compressed form as the key. The
+// backing map lives behind the relay's existing `State` mutex; it could later be swapped for a
+// durable store so protection survives restarts.
+#[derive(Debug, Default)]
+struct ProposalProtection {
+    revealed_blocks: HashMap<(Slot, BlsPublicKey), Root>,
+}
+
+impl ProposalProtection {
+    // Record that `block_root` is being revealed for `(slot, proposer)`, returning
+    // `Error::Equivocation` if a different block root has already been revealed for that pair.
+    fn register_reveal(
+        &mut self,
+        slot: Slot,
+        proposer: &BlsPublicKey,
+        block_root: Root,
+    ) -> Result<(), Error> {
+        match self.revealed_blocks.entry((slot, proposer.clone())) {
+            Entry::Occupied(entry) => {
+                if entry.get() != &block_root {
+                    return Err(Error::Equivocation)
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(block_root);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Relay {
@@ -145,6 +293,20 @@ impl Relay {
         beacon_node: Client,
         secret_key: SecretKey,
         context: Arc<Context>,
+    ) -> Self {
+        Self::with_builder(genesis_validators_root, beacon_node, secret_key, context, NullBuilder)
+    }
+}
+
+impl<B: Builder> Relay<B> {
+    // Construct a relay backed by an arbitrary [`Builder`]. Used in tests to inject a
+    // fault-injecting builder; production callers use [`Relay::new`].
+    pub fn with_builder(
+        genesis_validators_root: Root,
+        beacon_node: Client,
+        secret_key: SecretKey,
+        context: Arc<Context>,
+        builder: B,
     ) -> Self {
         let public_key = secret_key.public_key();
         let validator_registry = ValidatorRegistry::new(beacon_node);
@@ -152,7 +314,7 @@ impl Relay {
             secret_key,
             public_key,
             genesis_validators_root,
-            builder: NullBuilder,
+            builder,
             validator_registry,
             context,
             state: Default::default(),
@@ -179,11 +341,17 @@ impl Relay {
         state
             .execution_payloads
             .retain(|bid_request, _| bid_request.slot + PROPOSAL_TOLERANCE_DELAY >= slot);
+        state
+            .blobs_bundles
+            .retain(|bid_request, _| bid_request.slot + PROPOSAL_TOLERANCE_DELAY >= slot);
+        state
+            .parent_gas_limits
+            .retain(|_, (served_slot, _)| *served_slot + PROPOSAL_TOLERANCE_DELAY >= slot);
     }
 }
 
 #[async_trait]
-impl BlindedBlockProvider for Relay {
+impl<B: Builder + Send + Sync + 'static> BlindedBlockProvider for Relay<B> {
     async fn register_validators(
         &self,
         registrations: &mut [SignedValidatorRegistration],
@@ -205,21 +373,40 @@ impl BlindedBlockProvider for Relay {
             .validator_registry
             .get_preferences(public_key)
             .ok_or_else(|| Error::MissingPreferences(public_key.clone()))?;
-        let (mut payload, value) = self.builder.get_payload_with_value(
-            bid_request,
-            &preferences.fee_recipient,
-            preferences.gas_limit,
-            &self.context,
-        )?;
+        let (mut payload, blobs_bundle, value, builder_parent_gas_limit) =
+            self.builder.get_payload_with_value(
+                bid_request,
+                &preferences.fee_recipient,
+                preferences.gas_limit,
+                &self.context,
+            )?;
 
         let header = {
             let mut state = self.state.lock();
 
-            validate_execution_payload(&payload, &value, &preferences)?;
+            // Prefer the parent gas limit the relay recorded when it served the parent block;
+            // only fall back to the builder's self-reported value when the parent is unknown
+            // (e.g. the first block the relay serves on a chain).
+            let parent_gas_limit = state
+                .parent_gas_limits
+                .get(&bid_request.parent_hash)
+                .map(|(_, gas_limit)| *gas_limit)
+                .unwrap_or(builder_parent_gas_limit);
+
+            validate_execution_payload(&payload, &value, &preferences, parent_gas_limit)?;
+            validate_blobs_bundle(&blobs_bundle, &self.context)?;
 
             let header = ExecutionPayloadHeader::try_from(&mut payload)?;
 
+            state
+                .parent_gas_limits
+                .insert(payload.block_hash().clone(), (bid_request.slot, payload.gas_limit()));
             state.execution_payloads.insert(bid_request.clone(), payload);
+            // Only Deneb-and-later bids carry blobs; storing an empty bundle for a pre-Deneb bid
+            // would make `open_bid` demand blob commitments the blinded block cannot have.
+            if !blobs_bundle.blobs.is_empty() {
+                state.blobs_bundles.insert(bid_request.clone(), blobs_bundle.clone());
+            }
             header
         };
 
@@ -240,14 +427,26 @@ impl BlindedBlockProvider for Relay {
                 let signed_bid = capella::SignedBuilderBid { message: bid, signature };
                 Ok(SignedBuilderBid::Capella(signed_bid))
             }
-            ExecutionPayloadHeader::Deneb(_header) => unimplemented!(),
+            ExecutionPayloadHeader::Deneb(header) => {
+                let blob_kzg_commitments = blobs_bundle.commitments.clone();
+                let mut bid = deneb::BuilderBid {
+                    header,
+                    blob_kzg_commitments,
+                    value,
+                    public_key: self.public_key.clone(),
+                };
+                let signature = sign_builder_message(&mut bid, &self.secret_key, &self.context)?;
+
+                let signed_bid = deneb::SignedBuilderBid { message: bid, signature };
+                Ok(SignedBuilderBid::Deneb(signed_bid))
+            }
         }
     }
 
     async fn open_bid(
         &self,
         signed_block: &mut SignedBlindedBeaconBlock,
-    ) -> Result<ExecutionPayload, Error> {
+    ) -> Result<(ExecutionPayload, Option<BlobsBundle>), Error> {
         let slot = signed_block.slot();
         let parent_hash = signed_block.parent_hash().clone();
         let proposer_index = signed_block.proposer_index();
@@ -255,9 +454,12 @@ impl BlindedBlockProvider for Relay {
             self.validator_registry.get_public_key(proposer_index).map_err(Error::from)?;
         let bid_request = BidRequest { slot, parent_hash, public_key };
 
-        let payload = {
+        let (payload, blobs_bundle) = {
             let mut state = self.state.lock();
-            state.execution_payloads.remove(&bid_request).ok_or(Error::UnknownBid)?
+            let payload =
+                state.execution_payloads.remove(&bid_request).ok_or(Error::UnknownBid)?;
+            let blobs_bundle = state.blobs_bundles.remove(&bid_request);
+            (payload, blobs_bundle)
         };
 
         validate_signed_block(
@@ -268,6 +470,336 @@ impl BlindedBlockProvider for Relay {
             &self.context,
         )?;
 
-        Ok(payload)
+        // Ensure the proposer signed over exactly the commitments the relay bid with, so a
+        // builder cannot swap the blobs out from under the commitment in the builder bid.
+        if let Some(blobs_bundle) = blobs_bundle.as_ref() {
+            let signed_commitments =
+                signed_block.blob_kzg_commitments().ok_or(Error::InvalidBlobsBundle)?;
+            if signed_commitments.as_slice() != blobs_bundle.commitments.as_slice() {
+                return Err(Error::InvalidBlobsBundle)
+            }
+        }
+
+        // Only once every check has passed do we record the reveal: a block we reject must not
+        // burn the `(slot, proposer)` slot in the protection map, or a later well-formed reveal
+        // for the same pair would be wrongly refused as an equivocation.
+        let block_root = signed_block.hash_tree_root().map_err(Error::from)?;
+        {
+            let mut state = self.state.lock();
+            state.proposal_protection.register_reveal(
+                bid_request.slot,
+                &bid_request.public_key,
+                block_root,
+            )?;
+        }
+
+        Ok((payload, blobs_bundle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_api_client::mainnet::Client;
+    use ethereum_consensus::{
+        builder::ValidatorRegistration,
+        capella::mainnet::{self as capella_spec, Withdrawal},
+        primitives::ExecutionAddress,
+    };
+    use mev_build_rs::{Builder, MockBuilder};
+
+    const PARENT_GAS_LIMIT: u64 = 30_000_000;
+
+    fn preferences(gas_limit: u64) -> ValidatorRegistration {
+        ValidatorRegistration { gas_limit, ..Default::default() }
+    }
+
+    fn payload_of(builder: &MockBuilder) -> (ExecutionPayload, U256, u64) {
+        let (payload, _blobs, value, parent_gas_limit) = builder
+            .get_payload_with_value(
+                &BidRequest::default(),
+                &Default::default(),
+                PARENT_GAS_LIMIT,
+                &Context::for_mainnet(),
+            )
+            .unwrap();
+        (payload, value, parent_gas_limit)
+    }
+
+    // Build a Capella payload carrying `withdrawals`, used to exercise the withdrawals guards.
+    fn capella_payload_with_withdrawals(withdrawals: Vec<Withdrawal>) -> ExecutionPayload {
+        let mut payload = capella_spec::ExecutionPayload {
+            gas_limit: PARENT_GAS_LIMIT,
+            ..Default::default()
+        };
+        for withdrawal in withdrawals {
+            let _ = payload.withdrawals.push(withdrawal);
+        }
+        ExecutionPayload::Capella(payload)
+    }
+
+    // Wrap a Capella execution payload header into a signed (blinded) block with a default
+    // signature, mirroring how the relay constructs its own signed bids.
+    fn signed_block_from_header(
+        header: capella_spec::ExecutionPayloadHeader,
+    ) -> SignedBlindedBeaconBlock {
+        let body =
+            capella::BlindedBeaconBlockBody { execution_payload_header: header, ..Default::default() };
+        let message = capella::BlindedBeaconBlock { body, ..Default::default() };
+        SignedBlindedBeaconBlock::Capella(capella::SignedBlindedBeaconBlock {
+            message,
+            ..Default::default()
+        })
+    }
+
+    fn capella_header(payload: &ExecutionPayload) -> capella_spec::ExecutionPayloadHeader {
+        let mut payload = payload.clone();
+        match ExecutionPayloadHeader::try_from(&mut payload).unwrap() {
+            ExecutionPayloadHeader::Capella(header) => header,
+            _ => panic!("expected a Capella header"),
+        }
+    }
+
+    #[test]
+    fn accepts_bid_on_parent_gas_limit() {
+        for builder in [
+            MockBuilder::bellatrix(PARENT_GAS_LIMIT, U256::from(1)),
+            MockBuilder::capella(PARENT_GAS_LIMIT, U256::from(1)),
+        ] {
+            let (payload, value, parent_gas_limit) = payload_of(&builder);
+            assert!(validate_execution_payload(
+                &payload,
+                &value,
+                &preferences(PARENT_GAS_LIMIT),
+                parent_gas_limit,
+            )
+            .is_ok());
+        }
+    }
+
+    #[test]
+    fn accepts_both_window_extremes() {
+        // The two limits at the very edge of the adjustment window are legitimate and must be
+        // accepted.
+        let max_delta = PARENT_GAS_LIMIT / 1024;
+        for edge in [PARENT_GAS_LIMIT - max_delta + 1, PARENT_GAS_LIMIT + max_delta - 1] {
+            let mut builder = MockBuilder::bellatrix(PARENT_GAS_LIMIT, U256::from(1));
+            *builder.gas_limit_mut() = edge;
+            let (payload, value, parent_gas_limit) = payload_of(&builder);
+            assert!(validate_execution_payload(
+                &payload,
+                &value,
+                &preferences(edge),
+                parent_gas_limit,
+            )
+            .is_ok());
+        }
+    }
+
+    #[test]
+    fn does_not_panic_on_degenerate_parent_gas_limit() {
+        // A parent limit below the 1024 divisor collapses the window to the parent itself rather
+        // than underflowing.
+        let mut builder = MockBuilder::bellatrix(512, U256::from(1));
+        *builder.gas_limit_mut() = 512;
+        let (payload, value, _) = payload_of(&builder);
+        assert!(validate_execution_payload(&payload, &value, &preferences(512), 512).is_ok());
+    }
+
+    #[test]
+    fn rejects_gas_limit_outside_adjustment_window() {
+        for mut builder in [
+            MockBuilder::bellatrix(PARENT_GAS_LIMIT, U256::from(1)),
+            MockBuilder::capella(PARENT_GAS_LIMIT, U256::from(1)),
+        ] {
+            // A full 1/512 jump is twice the permitted step, so the payload must be rejected even
+            // though the proposer's preference asks for it.
+            let out_of_window = PARENT_GAS_LIMIT + PARENT_GAS_LIMIT / 512;
+            *builder.gas_limit_mut() = out_of_window;
+            let (payload, value, parent_gas_limit) = payload_of(&builder);
+            assert!(matches!(
+                validate_execution_payload(
+                    &payload,
+                    &value,
+                    &preferences(out_of_window),
+                    parent_gas_limit,
+                ),
+                Err(Error::InvalidGasLimit)
+            ));
+        }
+    }
+
+    #[test]
+    fn clamps_aggressive_preference_into_window() {
+        // The proposer asks for a gas limit far above the window; the relay only accepts a payload
+        // that moves by the maximal permitted step toward it.
+        let max_delta = PARENT_GAS_LIMIT / 1024;
+        let clamped = PARENT_GAS_LIMIT + max_delta - 1;
+        let mut builder = MockBuilder::bellatrix(PARENT_GAS_LIMIT, U256::from(1));
+        *builder.gas_limit_mut() = clamped;
+        let (payload, value, parent_gas_limit) = payload_of(&builder);
+        assert!(validate_execution_payload(
+            &payload,
+            &value,
+            &preferences(PARENT_GAS_LIMIT * 2),
+            parent_gas_limit,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn rejects_omitted_proposer_payment() {
+        // A builder that reports a zero payment has not paid the proposer; the bid must be
+        // rejected before it is ever signed.
+        let mut builder = MockBuilder::capella(PARENT_GAS_LIMIT, U256::from(1));
+        *builder.value_mut() = U256::ZERO;
+        let (payload, value, parent_gas_limit) = payload_of(&builder);
+        assert!(matches!(
+            validate_execution_payload(
+                &payload,
+                &value,
+                &preferences(PARENT_GAS_LIMIT),
+                parent_gas_limit,
+            ),
+            Err(Error::InvalidPayment)
+        ));
+    }
+
+    #[test]
+    fn rejects_non_increasing_withdrawal_indices() {
+        let payload = capella_payload_with_withdrawals(vec![
+            Withdrawal { index: 5, ..Default::default() },
+            Withdrawal { index: 3, ..Default::default() },
+        ]);
+        assert!(matches!(
+            validate_execution_payload(&payload, &U256::from(1), &preferences(PARENT_GAS_LIMIT), PARENT_GAS_LIMIT),
+            Err(Error::InvalidWithdrawals)
+        ));
+    }
+
+    #[test]
+    fn rejects_block_hash_mismatch() {
+        // The signed block commits to a different block hash than the payload the relay holds.
+        let mut builder = MockBuilder::capella(PARENT_GAS_LIMIT, U256::from(1));
+        *builder.block_hash_mut() = Hash32::try_from([1u8; 32].as_ref()).unwrap();
+        let (local_payload, ..) = payload_of(&builder);
+
+        let mut signed_block = signed_block_from_header(Default::default());
+        let result = validate_signed_block(
+            &mut signed_block,
+            &BlsPublicKey::default(),
+            &local_payload,
+            &Root::default(),
+            &Context::for_mainnet(),
+        );
+        assert!(matches!(result, Err(Error::UnknownBlock)));
+    }
+
+    #[test]
+    fn rejects_withdrawals_root_mismatch() {
+        // Block hashes agree, but the withdrawals in the revealed payload do not match the
+        // withdrawals root the proposer signed over.
+        let local_payload =
+            capella_payload_with_withdrawals(vec![Withdrawal { index: 0, ..Default::default() }]);
+        // The signed header carries the default (empty-list) withdrawals root and a matching
+        // (default) block hash.
+        let mut signed_block = signed_block_from_header(capella_header(&capella_payload_with_withdrawals(vec![])));
+        let result = validate_signed_block(
+            &mut signed_block,
+            &BlsPublicKey::default(),
+            &local_payload,
+            &Root::default(),
+            &Context::for_mainnet(),
+        );
+        assert!(matches!(result, Err(Error::InvalidWithdrawals)));
+    }
+
+    #[test]
+    fn rejects_tampered_signature() {
+        // Block hash and withdrawals agree, so validation reaches the signature check; a block
+        // signed with a bogus (default) signature must be rejected.
+        let local_payload = capella_payload_with_withdrawals(vec![]);
+        let mut signed_block = signed_block_from_header(capella_header(&local_payload));
+        let result = validate_signed_block(
+            &mut signed_block,
+            &BlsPublicKey::default(),
+            &local_payload,
+            &Root::default(),
+            &Context::for_mainnet(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn short_circuits_empty_blobs_bundle() {
+        // A pre-Deneb bid carries an empty bundle and must validate without loading the KZG setup.
+        assert!(validate_blobs_bundle(&BlobsBundle::default(), &Context::for_mainnet()).is_ok());
+    }
+
+    #[test]
+    fn rejects_mismatched_blobs_bundle_lengths() {
+        let mut bundle = BlobsBundle::default();
+        let _ = bundle.commitments.push(Default::default());
+        assert!(matches!(
+            validate_blobs_bundle(&bundle, &Context::for_mainnet()),
+            Err(Error::InvalidBlobsBundle)
+        ));
+    }
+
+    #[test]
+    fn refuses_to_reveal_second_block_for_slot() {
+        let mut protection = ProposalProtection::default();
+        let proposer = BlsPublicKey::default();
+        let first = Root::try_from([1u8; 32].as_ref()).unwrap();
+        let second = Root::try_from([2u8; 32].as_ref()).unwrap();
+        assert!(protection.register_reveal(7, &proposer, first).is_ok());
+        // Re-revealing the same block is idempotent...
+        assert!(protection.register_reveal(7, &proposer, first).is_ok());
+        // ...but a different block for the same slot/proposer is an equivocation.
+        assert!(matches!(
+            protection.register_reveal(7, &proposer, second),
+            Err(Error::Equivocation)
+        ));
+    }
+
+    #[tokio::test]
+    async fn fetch_best_bid_rejects_pre_bellatrix_slot() {
+        // Drive the `BlindedBlockProvider` path through a relay backed by the `MockBuilder`: a
+        // default (slot 0) request is pre-Bellatrix and must be rejected before any payload work.
+        let relay = Relay::with_builder(
+            Root::default(),
+            Client::new("http://localhost:5052".parse().unwrap()),
+            SecretKey::default(),
+            Arc::new(Context::for_mainnet()),
+            MockBuilder::bellatrix(PARENT_GAS_LIMIT, U256::from(1)),
+        );
+        assert!(matches!(
+            relay.fetch_best_bid(&BidRequest::default()).await,
+            Err(Error::UnsupportedFork(_))
+        ));
+    }
+
+    #[test]
+    fn builder_fields_flow_into_produced_payload() {
+        // The fee recipient and parent hash are committed to via the signed header and the
+        // validator's registration rather than guarded at build time; confirm the accessors
+        // thread the chosen values into the produced payload so tests can target those commitments.
+        let fee_recipient = ExecutionAddress::try_from([9u8; 20].as_ref()).unwrap();
+        let parent_hash = Hash32::try_from([7u8; 32].as_ref()).unwrap();
+        let mut builder = MockBuilder::capella(PARENT_GAS_LIMIT, U256::from(1));
+        *builder.fee_recipient_mut() = fee_recipient.clone();
+        *builder.parent_hash_mut() = parent_hash.clone();
+        let (payload, ..) = payload_of(&builder);
+        assert_eq!(payload.fee_recipient(), &fee_recipient);
+        assert_eq!(payload.parent_hash(), &parent_hash);
+    }
+
+    #[test]
+    fn maps_slot_to_fork() {
+        let context = Context::for_mainnet();
+        let slot = context.capella_fork_epoch * context.slots_per_epoch;
+        assert_eq!(fork_for_slot(slot, &context).unwrap(), Fork::Capella);
+        let pre_merge = context.bellatrix_fork_epoch * context.slots_per_epoch - 1;
+        assert!(matches!(fork_for_slot(pre_merge, &context), Err(Error::UnsupportedFork(_))));
     }
 }